@@ -47,6 +47,47 @@ fn iri_parse(c: &mut Criterion) {
     });
 }
 
+fn iri_parse_long_query(c: &mut Criterion) {
+    let examples = [
+        format!(
+            "http://example.com/search?{}",
+            "q=some+search+terms&page=42&".repeat(100)
+        ),
+        format!(
+            "http://example.com/log?{}",
+            "ts=2024-01-01T00:00:00Z;ua=Mozilla/5.0,".repeat(100)
+        ),
+        format!(
+            "http://example.com/search?{}#top",
+            "q=caf%C3%A9&lang=fr&".repeat(100)
+        ),
+    ];
+    c.bench_function("Iri::parse long query", |b| {
+        b.iter(|| {
+            for iri in &examples {
+                Iri::parse(iri.as_str()).unwrap();
+            }
+        })
+    });
+}
+
+fn iri_parse_control_characters(c: &mut Criterion) {
+    let examples = [
+        format!("http://example.com/{}\n", "a/".repeat(100_000)),
+        format!("http://example.com/?{}\0", "q=1&".repeat(100_000)),
+        format!("http://example.com/{}\r\n", "\u{e9}".repeat(100_000)),
+        format!("http://example.com/#{}\t", "f".repeat(100_000)),
+        "\u{1}".repeat(1_000_000),
+    ];
+    c.bench_function("Iri::parse with control characters", |b| {
+        b.iter(|| {
+            for iri in &examples {
+                Iri::parse(iri.as_str()).unwrap_err();
+            }
+        })
+    });
+}
+
 fn iri_parse_relative(c: &mut Criterion) {
     c.bench_function("IriRef::parse", |b| {
         b.iter(|| {
@@ -64,6 +105,23 @@ fn iri_parse_relative(c: &mut Criterion) {
     });
 }
 
+fn iri_normalize(c: &mut Criterion) {
+    c.bench_function("Iri::parse_normalized", |b| {
+        b.iter(|| {
+            for iri in abs_examples().iter() {
+                Iri::parse_normalized(iri).unwrap();
+            }
+        })
+    });
+    c.bench_function("Iri::parse + Iri::normalize", |b| {
+        b.iter(|| {
+            for iri in abs_examples().iter() {
+                Iri::parse(*iri).unwrap().normalize();
+            }
+        })
+    });
+}
+
 fn iri_resolve(c: &mut Criterion) {
     let examples = [
         "g:h",
@@ -113,6 +171,24 @@ fn iri_resolve(c: &mut Criterion) {
 
     let base = Iri::parse("http://a/b/c/d;p?q").unwrap();
 
+    c.bench_function("Iri::resolve", |b| {
+        b.iter(|| {
+            for relative in examples.iter() {
+                base.resolve(relative).unwrap();
+            }
+        })
+    });
+    c.bench_function("Iri::resolve_reusing", |b| {
+        let mut scratch = Some(base.resolve("").unwrap());
+        b.iter(|| {
+            for relative in examples.iter() {
+                scratch = Some(
+                    base.resolve_reusing(relative, scratch.take().unwrap())
+                        .unwrap(),
+                );
+            }
+        })
+    });
     let mut buf = String::new();
     c.bench_function("Iri::resolve_into", |b| {
         b.iter(|| {
@@ -132,6 +208,24 @@ fn iri_resolve(c: &mut Criterion) {
     });
 }
 
+fn iri_resolve_dot_segments(c: &mut Criterion) {
+    let base = Iri::parse(format!("http://example.com/{}d?q", "segment/".repeat(100))).unwrap();
+    let examples = [
+        format!("{}g", "../".repeat(100)),
+        format!("{}g", "../".repeat(200)),
+        format!("{}g", "a/b/../../".repeat(100)),
+        format!("{}g", "./".repeat(200)),
+        format!("/{}", "a/../".repeat(100)),
+    ];
+    c.bench_function("Iri::resolve dot segments", |b| {
+        b.iter(|| {
+            for relative in &examples {
+                base.resolve(relative).unwrap();
+            }
+        })
+    });
+}
+
 fn iri_relativize(c: &mut Criterion) {
     let base = Iri::parse("http://a/b/c/d;p?q").unwrap();
     let examples = [
@@ -195,8 +289,12 @@ fn iri_relativize(c: &mut Criterion) {
 criterion_group!(
     iri,
     iri_parse,
+    iri_parse_long_query,
+    iri_parse_control_characters,
     iri_parse_relative,
+    iri_normalize,
     iri_resolve,
+    iri_resolve_dot_segments,
     iri_relativize
 );
 
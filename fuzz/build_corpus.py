@@ -286,5 +286,7 @@ def write_corpus(name, examples):
 
 
 write_corpus("parse", examples)
+write_corpus("normalize_eq", examples)
+write_corpus("accessors", examples)
 write_corpus("resolve", example_pairs)
 write_corpus("relativize", example_pairs)
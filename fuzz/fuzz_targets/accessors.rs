@@ -0,0 +1,44 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use oxiri::IriRef;
+use std::str;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(s) = str::from_utf8(data) else {
+        return;
+    };
+    let unchecked = IriRef::parse_unchecked(s);
+    let Ok(iri) = IriRef::parse(s) else {
+        return;
+    };
+    assert_eq!(iri.userinfo(), unchecked.userinfo());
+    assert_eq!(iri.host(), unchecked.host());
+    assert_eq!(iri.port(), unchecked.port());
+    assert!(iri.path_segments().eq(unchecked.path_segments()));
+
+    // The authority is made of the user information, the host and the port
+    if let Some(authority) = iri.authority() {
+        let mut expected = String::new();
+        if let Some(userinfo) = iri.userinfo() {
+            expected.push_str(userinfo);
+            expected.push('@');
+        }
+        expected.push_str(iri.host().unwrap());
+        // The port is kept raw because it might be empty, have leading zeros, or not fit in a u16
+        let port = authority.strip_prefix(expected.as_str()).unwrap();
+        if port.is_empty() {
+            assert_eq!(iri.port(), None);
+        } else {
+            let port = port.strip_prefix(':').unwrap();
+            assert!(port.bytes().all(|b| b.is_ascii_digit()));
+            assert_eq!(iri.port(), port.parse().ok());
+        }
+    } else {
+        assert_eq!(iri.userinfo(), None);
+        assert_eq!(iri.host(), None);
+        assert_eq!(iri.port(), None);
+    }
+
+    // The path is made of its segments
+    assert_eq!(iri.path_segments().collect::<Vec<_>>().join("/"), iri.path());
+});
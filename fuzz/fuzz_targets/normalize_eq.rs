@@ -0,0 +1,33 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use oxiri::Iri;
+use std::str;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(s) = str::from_utf8(data) else {
+        return;
+    };
+    let Ok(iri) = Iri::parse(s) else {
+        return;
+    };
+    let normalized = iri.normalize();
+    // We check that the single pass normalization gives the same result
+    assert_eq!(Iri::parse_normalized(s).unwrap(), normalized);
+    // We check that normalization is idempotent so the original and normalized IRIs are equivalent
+    assert_eq!(normalized.normalize(), normalized);
+    // We check that the normal form detection is consistent with the normalization
+    assert_eq!(iri.is_normalized(), iri == normalized);
+    assert!(normalized.is_normalized());
+    // We check that the normalized comparison is consistent with the normalization
+    assert!(iri.eq_normalized(&iri));
+    assert!(iri.eq_normalized(&normalized));
+    assert!(normalized.eq_normalized(&iri));
+    // We check that the normalized IRI is valid and its components are the ones found by re-parsing
+    let parsed = Iri::parse(normalized.as_str()).unwrap();
+    assert_eq!(normalized, parsed);
+    assert_eq!(normalized.scheme(), parsed.scheme());
+    assert_eq!(normalized.authority(), parsed.authority());
+    assert_eq!(normalized.path(), parsed.path());
+    assert_eq!(normalized.query(), parsed.query());
+    assert_eq!(normalized.fragment(), parsed.fragment());
+});
@@ -18,4 +18,13 @@ fuzz_target!(|data: &[u8]| {
     assert_eq!(iri.path(), unchecked.path());
     assert_eq!(iri.query(), unchecked.query());
     assert_eq!(iri.fragment(), unchecked.fragment());
+    assert_eq!(iri.path_is_percent_encoded(), iri.path().contains('%'));
+    assert_eq!(
+        iri.query_is_percent_encoded(),
+        iri.query().map_or(false, |q| q.contains('%'))
+    );
+    assert_eq!(
+        iri.fragment_is_percent_encoded(),
+        iri.fragment().map_or(false, |f| f.contains('%'))
+    );
 });
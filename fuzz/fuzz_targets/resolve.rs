@@ -28,4 +28,13 @@ fuzz_target!(|data: &[u8]| {
     assert_eq!(valid.path(), unchecked.path());
     assert_eq!(valid.query(), unchecked.query());
     assert_eq!(valid.fragment(), unchecked.fragment());
+    assert_eq!(valid.path_is_percent_encoded(), valid.path().contains('%'));
+    assert_eq!(
+        valid.query_is_percent_encoded(),
+        valid.query().map_or(false, |q| q.contains('%'))
+    );
+    assert_eq!(
+        valid.fragment_is_percent_encoded(),
+        valid.fragment().map_or(false, |f| f.contains('%'))
+    );
 });
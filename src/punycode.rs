@@ -0,0 +1,98 @@
+//! [Punycode](https://www.rfc-editor.org/rfc/rfc3492.html) decoding of internationalized domain name labels.
+
+const BASE: u32 = 36;
+const T_MIN: u32 = 1;
+const T_MAX: u32 = 26;
+const SKEW: u32 = 38;
+const DAMP: u32 = 700;
+const INITIAL_BIAS: u32 = 72;
+const INITIAL_N: u32 = 128;
+
+/// Decodes the host labels starting with `xn--` into Unicode
+///
+/// Returns `None` if there is no such label or if one of them is not valid Punycode.
+pub fn decode_host(host: &str) -> Option<String> {
+    if !host.split('.').any(is_ace_label) {
+        return None;
+    }
+    let mut output = String::with_capacity(host.len());
+    for (i, label) in host.split('.').enumerate() {
+        if i > 0 {
+            output.push('.');
+        }
+        if is_ace_label(label) {
+            output.extend(decode(&label[4..])?);
+        } else {
+            output.push_str(label);
+        }
+    }
+    Some(output)
+}
+
+fn is_ace_label(label: &str) -> bool {
+    label
+        .get(..4)
+        .map_or(false, |prefix| prefix.eq_ignore_ascii_case("xn--"))
+}
+
+/// Decodes a Punycode string following the RFC 3492 section 6.2 algorithm
+fn decode(input: &str) -> Option<Vec<char>> {
+    let (basic, extended) = input.rsplit_once('-').unwrap_or(("", input));
+    if !basic.is_ascii() || extended.is_empty() {
+        // A label encoding only basic code points is not valid IDNA
+        return None;
+    }
+    let mut output = basic.chars().collect::<Vec<_>>();
+    let mut n = INITIAL_N;
+    let mut i = 0_u32;
+    let mut bias = INITIAL_BIAS;
+    let mut digits = extended.bytes();
+    while digits.len() > 0 {
+        let old_i = i;
+        let mut w = 1_u32;
+        let mut k = BASE;
+        loop {
+            let digit = decode_digit(digits.next()?)?;
+            i = i.checked_add(digit.checked_mul(w)?)?;
+            let t = if k <= bias {
+                T_MIN
+            } else if k >= bias + T_MAX {
+                T_MAX
+            } else {
+                k - bias
+            };
+            if digit < t {
+                break;
+            }
+            w = w.checked_mul(BASE - t)?;
+            k += BASE;
+        }
+        let len = u32::try_from(output.len()).ok()? + 1;
+        bias = adapt(i - old_i, len, old_i == 0);
+        n = n.checked_add(i / len)?;
+        i %= len;
+        output.insert(usize::try_from(i).ok()?, char::from_u32(n)?);
+        i += 1;
+    }
+    Some(output)
+}
+
+fn decode_digit(digit: u8) -> Option<u32> {
+    match digit {
+        b'a'..=b'z' => Some(u32::from(digit - b'a')),
+        b'A'..=b'Z' => Some(u32::from(digit - b'A')),
+        b'0'..=b'9' => Some(u32::from(digit - b'0') + 26),
+        _ => None,
+    }
+}
+
+fn adapt(delta: u32, num_points: u32, first_time: bool) -> u32 {
+    let mut delta = if first_time { delta / DAMP } else { delta / 2 };
+    delta += delta / num_points;
+    let mut k = 0;
+    while delta > ((BASE - T_MIN) * T_MAX) / 2 {
+        delta /= BASE - T_MIN;
+        k += BASE;
+    }
+    k + (BASE - T_MIN + 1) * delta / (delta + SKEW)
+}
@@ -0,0 +1,182 @@
+//! [Serde](https://serde.rs/) (de)serialization of [`Iri`] and [`IriRef`] as byte strings.
+//!
+//! It is meant to be used with the `#[serde(with = "oxiri::serde_bytes")]` field attribute
+//! for formats where byte strings are more compact or idiomatic than strings, like CBOR.
+//! The deserialization validates that the bytes are UTF-8 then that they are a valid IRI.
+//! Strings are also accepted on deserialization.
+//!
+//! ```
+//! use oxiri::Iri;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Resource {
+//!     #[serde(with = "oxiri::serde_bytes")]
+//!     id: Iri<String>,
+//! }
+//! ```
+
+use crate::{Iri, IriRef};
+use serde::de::{Error, SeqAccess, Unexpected, Visitor};
+use serde::{Deserializer, Serializer};
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::Deref;
+use std::str;
+
+/// The types that the functions of this module (de)serialize:
+/// [`Iri`] and [`IriRef`] storing their text in a [`String`] or in a `&str` borrowed from the input.
+pub trait IriBytes<'de>: private::Sealed<'de> {}
+
+impl<'de, I: private::Sealed<'de>> IriBytes<'de> for I {}
+
+/// Serializes the IRI as a byte string.
+pub fn serialize<'de, I: IriBytes<'de>, S: Serializer>(
+    iri: &I,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_bytes(iri.as_str().as_bytes())
+}
+
+/// Deserializes the IRI from a byte string, or a string, validating it.
+pub fn deserialize<'de, I: IriBytes<'de>, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<I, D::Error> {
+    deserializer.deserialize_bytes(IriBytesVisitor(PhantomData))
+}
+
+struct IriBytesVisitor<I>(PhantomData<I>);
+
+impl<'de, I: IriBytes<'de>> Visitor<'de> for IriBytesVisitor<I> {
+    type Value = I;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a byte string containing an IRI")
+    }
+
+    fn visit_bytes<E: Error>(self, v: &[u8]) -> Result<I, E> {
+        self.visit_str(str::from_utf8(v).map_err(E::custom)?)
+    }
+
+    fn visit_borrowed_bytes<E: Error>(self, v: &'de [u8]) -> Result<I, E> {
+        self.visit_borrowed_str(str::from_utf8(v).map_err(E::custom)?)
+    }
+
+    fn visit_byte_buf<E: Error>(self, v: Vec<u8>) -> Result<I, E> {
+        self.visit_string(String::from_utf8(v).map_err(E::custom)?)
+    }
+
+    fn visit_str<E: Error>(self, v: &str) -> Result<I, E> {
+        I::from_transient_str(v)
+    }
+
+    fn visit_borrowed_str<E: Error>(self, v: &'de str) -> Result<I, E> {
+        I::from_borrowed_str(v)
+    }
+
+    fn visit_string<E: Error>(self, v: String) -> Result<I, E> {
+        I::from_string(v)
+    }
+
+    // Some formats like JSON encode byte strings as sequences of integers
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<I, A::Error> {
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(byte) = seq.next_element()? {
+            bytes.push(byte);
+        }
+        self.visit_byte_buf(bytes)
+    }
+}
+
+mod private {
+    use super::*;
+
+    pub trait Sealed<'de>: Sized {
+        fn as_str(&self) -> &str;
+
+        fn from_transient_str<E: Error>(iri: &str) -> Result<Self, E>;
+
+        fn from_borrowed_str<E: Error>(iri: &'de str) -> Result<Self, E>;
+
+        fn from_string<E: Error>(iri: String) -> Result<Self, E>;
+    }
+
+    /// The storages of the IRI text
+    pub trait Storage<'de>: Deref<Target = str> + Sized {
+        fn from_transient_str<E: Error>(iri: &str) -> Result<Self, E>;
+
+        fn from_borrowed_str<E: Error>(iri: &'de str) -> Result<Self, E>;
+
+        fn from_string<E: Error>(iri: String) -> Result<Self, E>;
+    }
+
+    impl<'de> Storage<'de> for String {
+        fn from_transient_str<E: Error>(iri: &str) -> Result<Self, E> {
+            Ok(iri.to_owned())
+        }
+
+        fn from_borrowed_str<E: Error>(iri: &'de str) -> Result<Self, E> {
+            Ok(iri.to_owned())
+        }
+
+        fn from_string<E: Error>(iri: String) -> Result<Self, E> {
+            Ok(iri)
+        }
+    }
+
+    impl<'de> Storage<'de> for &'de str {
+        fn from_transient_str<E: Error>(iri: &str) -> Result<Self, E> {
+            Err(E::invalid_type(
+                Unexpected::Str(iri),
+                &"a borrowed byte string",
+            ))
+        }
+
+        fn from_borrowed_str<E: Error>(iri: &'de str) -> Result<Self, E> {
+            Ok(iri)
+        }
+
+        fn from_string<E: Error>(iri: String) -> Result<Self, E> {
+            Err(E::invalid_type(
+                Unexpected::Str(&iri),
+                &"a borrowed byte string",
+            ))
+        }
+    }
+
+    impl<'de, T: Storage<'de>> Sealed<'de> for IriRef<T> {
+        fn as_str(&self) -> &str {
+            IriRef::as_str(self)
+        }
+
+        fn from_transient_str<E: Error>(iri: &str) -> Result<Self, E> {
+            IriRef::parse(T::from_transient_str(iri)?).map_err(E::custom)
+        }
+
+        fn from_borrowed_str<E: Error>(iri: &'de str) -> Result<Self, E> {
+            IriRef::parse(T::from_borrowed_str(iri)?).map_err(E::custom)
+        }
+
+        fn from_string<E: Error>(iri: String) -> Result<Self, E> {
+            IriRef::parse(T::from_string(iri)?).map_err(E::custom)
+        }
+    }
+
+    impl<'de, T: Storage<'de>> Sealed<'de> for Iri<T> {
+        fn as_str(&self) -> &str {
+            Iri::as_str(self)
+        }
+
+        fn from_transient_str<E: Error>(iri: &str) -> Result<Self, E> {
+            Iri::parse(T::from_transient_str(iri)?).map_err(E::custom)
+        }
+
+        fn from_borrowed_str<E: Error>(iri: &'de str) -> Result<Self, E> {
+            Iri::parse(T::from_borrowed_str(iri)?).map_err(E::custom)
+        }
+
+        fn from_string<E: Error>(iri: String) -> Result<Self, E> {
+            Iri::parse(T::from_string(iri)?).map_err(E::custom)
+        }
+    }
+}
@@ -0,0 +1,152 @@
+//! [Serde](https://serde.rs/) (de)serialization of [`Iri`] and [`IriRef`] alongside the positions of their components.
+//!
+//! This is the encoding the [`Serialize`] and [`Deserialize`] implementations of [`Iri`] and [`IriRef`]
+//! use with non human-readable formats like [bincode](https://docs.rs/bincode).
+//! This module allows to use it with all formats with the `#[serde(with = "oxiri::serde_positions")]` field attribute.
+//! The IRI is encoded as a tuple of its text and of the positions of its components.
+//!
+//! The deserialization does not parse the IRI text again,
+//! it only checks that the stored positions are consistent with it like [`IriRef::from_positions`].
+//! It should only be used with data written by this crate.
+//!
+//! ```
+//! use oxiri::Iri;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Resource {
+//!     #[serde(with = "oxiri::serde_positions")]
+//!     id: Iri<String>,
+//! }
+//! ```
+
+use crate::{Iri, IriElementsPositions, IriRef, PercentEncodedComponents};
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::ops::Deref;
+
+/// The types that the functions of this module (de)serialize:
+/// [`Iri`] and [`IriRef`] storing their text in a type that is itself (de)serializable.
+pub trait IriWithPositions<'de>: private::Sealed<'de> {}
+
+impl<'de, I: private::Sealed<'de>> IriWithPositions<'de> for I {}
+
+/// Serializes the IRI as a tuple of its text and of the positions of its components.
+pub fn serialize<'de, I: IriWithPositions<'de>, S: Serializer>(
+    iri: &I,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let iri = iri.as_iri_ref();
+    serialize_iri_ref(&iri.iri, &iri.positions, serializer)
+}
+
+/// Deserializes the IRI from a tuple of its text and of the positions of its components, validating both.
+pub fn deserialize<'de, I: IriWithPositions<'de>, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<I, D::Error> {
+    I::deserialize_with_positions(deserializer)
+}
+
+/// The serialized positions: the ends of the scheme and of the user information, the start and the end of the host,
+/// the ends of the authority, path and query and a bit set of the components containing percent-encoded characters
+type StoredPositions = (usize, usize, usize, usize, usize, usize, usize, u8);
+
+pub(crate) fn serialize_iri_ref<T: Serialize, S: Serializer>(
+    iri: &T,
+    positions: &IriElementsPositions,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let stored: StoredPositions = (
+        positions.scheme_end,
+        positions.userinfo_end,
+        positions.host_start,
+        positions.host_end,
+        positions.authority_end,
+        positions.path_end,
+        positions.query_end,
+        u8::from(positions.percent_encoded.path)
+            | u8::from(positions.percent_encoded.query) << 1
+            | u8::from(positions.percent_encoded.fragment) << 2,
+    );
+    (iri, stored).serialize(serializer)
+}
+
+pub(crate) fn deserialize_iri_ref<
+    'de,
+    T: Deref<Target = str> + Deserialize<'de>,
+    D: Deserializer<'de>,
+>(
+    deserializer: D,
+) -> Result<IriRef<T>, D::Error> {
+    let (
+        iri,
+        (
+            scheme_end,
+            userinfo_end,
+            host_start,
+            host_end,
+            authority_end,
+            path_end,
+            query_end,
+            percent_encoded,
+        ),
+    ) = <(T, StoredPositions)>::deserialize(deserializer)?;
+    let positions = IriElementsPositions {
+        scheme_end,
+        userinfo_end,
+        host_start,
+        host_end,
+        authority_end,
+        path_end,
+        query_end,
+        percent_encoded: PercentEncodedComponents {
+            path: percent_encoded & 1 != 0,
+            query: percent_encoded & 2 != 0,
+            fragment: percent_encoded & 4 != 0,
+        },
+    };
+    if percent_encoded > 7 || !positions.is_consistent_with(&iri) {
+        return Err(Error::custom(
+            "The IRI component positions are not consistent with the IRI",
+        ));
+    }
+    Ok(IriRef { iri, positions })
+}
+
+mod private {
+    use super::*;
+
+    pub trait Sealed<'de>: Sized {
+        fn as_iri_ref(&self) -> IriRef<&str>;
+
+        fn deserialize_with_positions<D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Self, D::Error>;
+    }
+
+    impl<'de, T: Deref<Target = str> + Deserialize<'de>> Sealed<'de> for IriRef<T> {
+        fn as_iri_ref(&self) -> IriRef<&str> {
+            self.as_ref()
+        }
+
+        fn deserialize_with_positions<D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Self, D::Error> {
+            deserialize_iri_ref(deserializer)
+        }
+    }
+
+    impl<'de, T: Deref<Target = str> + Deserialize<'de>> Sealed<'de> for Iri<T> {
+        fn as_iri_ref(&self) -> IriRef<&str> {
+            self.0.as_ref()
+        }
+
+        fn deserialize_with_positions<D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Self, D::Error> {
+            deserialize_iri_ref(deserializer)?
+                .try_into()
+                .map_err(Error::custom)
+        }
+    }
+}
@@ -0,0 +1,580 @@
+//! [URI Template](https://www.rfc-editor.org/rfc/rfc6570.html) expansion.
+//!
+//! All the operators and modifiers of the RFC 6570 level 4 are supported.
+//!
+//! ```
+//! use oxiri::uri_template::{UriTemplate, Value};
+//! use std::collections::HashMap;
+//!
+//! let template = UriTemplate::parse("http://example.com/{user}/profile{?q,lang}")?;
+//! let mut variables = HashMap::new();
+//! variables.insert("user", Value::from("foo bar"));
+//! variables.insert("q", Value::from("a&b"));
+//! assert_eq!(
+//!     template.expand(&variables)?.as_str(),
+//!     "http://example.com/foo%20bar/profile?q=a%26b"
+//! );
+//! # Result::<(), oxiri::uri_template::UriTemplateError>::Ok(())
+//! ```
+
+use crate::{percent_encode_into, Iri, IriParseError};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+/// A parsed [URI Template](https://www.rfc-editor.org/rfc/rfc6570.html).
+///
+/// The template expansion must be a valid absolute [`Iri`].
+///
+/// ```
+/// use oxiri::uri_template::{UriTemplate, Value};
+/// use std::collections::HashMap;
+///
+/// let template = UriTemplate::parse("http://example.com/search{?q}")?;
+/// let mut variables = HashMap::new();
+/// variables.insert("q", Value::from("Hello World!"));
+/// assert_eq!(
+///     template.expand(&variables)?.as_str(),
+///     "http://example.com/search?q=Hello%20World%21"
+/// );
+/// # Result::<(), oxiri::uri_template::UriTemplateError>::Ok(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UriTemplate {
+    template: String,
+    parts: Vec<TemplatePart>,
+}
+
+impl UriTemplate {
+    /// Parses a URI Template.
+    pub fn parse(template: &str) -> Result<Self, UriTemplateError> {
+        let mut parts = Vec::new();
+        let mut literal_start = 0;
+        let mut position = 0;
+        while let Some(c) = template[position..].chars().next() {
+            if c == '{' {
+                if literal_start < position {
+                    parts.push(TemplatePart::Literal(
+                        template[literal_start..position].into(),
+                    ));
+                }
+                let end = template[position..].find('}').ok_or(UriTemplateError {
+                    kind: UriTemplateErrorKind::UnclosedExpression { position },
+                })? + position;
+                parts.push(parse_expression(
+                    &template[position + 1..end],
+                    position + 1,
+                )?);
+                position = end + 1;
+                literal_start = position;
+            } else {
+                validate_literal_char(template, position, c)?;
+                position += c.len_utf8();
+            }
+        }
+        if literal_start < template.len() {
+            parts.push(TemplatePart::Literal(template[literal_start..].into()));
+        }
+        Ok(Self {
+            template: template.into(),
+            parts,
+        })
+    }
+
+    /// Returns the template text.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.template
+    }
+
+    /// Expands the template using the given variables.
+    ///
+    /// Missing variables are considered undefined.
+    pub fn expand(
+        &self,
+        variables: &HashMap<&str, Value>,
+    ) -> Result<Iri<String>, UriTemplateError> {
+        let mut output = String::with_capacity(self.template.len());
+        for part in &self.parts {
+            match part {
+                TemplatePart::Literal(literal) => output.push_str(literal),
+                TemplatePart::Expression {
+                    operator,
+                    variables: specs,
+                } => expand_expression(*operator, specs, variables, &mut output)?,
+            }
+        }
+        Iri::parse(output).map_err(|e| UriTemplateError {
+            kind: UriTemplateErrorKind::InvalidIri(e),
+        })
+    }
+}
+
+impl fmt::Display for UriTemplate {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.template.fmt(f)
+    }
+}
+
+/// A value of a [`UriTemplate`] variable.
+///
+/// Empty lists and associative arrays are considered undefined.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Value {
+    /// A string value.
+    String(String),
+    /// A list of strings.
+    List(Vec<String>),
+    /// An ordered list of key-value pairs.
+    AssociativeArray(Vec<(String, String)>),
+}
+
+impl Value {
+    fn is_defined(&self) -> bool {
+        match self {
+            Self::String(_) => true,
+            Self::List(l) => !l.is_empty(),
+            Self::AssociativeArray(a) => !a.is_empty(),
+        }
+    }
+}
+
+impl From<&str> for Value {
+    #[inline]
+    fn from(value: &str) -> Self {
+        Self::String(value.into())
+    }
+}
+
+impl From<String> for Value {
+    #[inline]
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<Vec<String>> for Value {
+    #[inline]
+    fn from(value: Vec<String>) -> Self {
+        Self::List(value)
+    }
+}
+
+impl From<Vec<(String, String)>> for Value {
+    #[inline]
+    fn from(value: Vec<(String, String)>) -> Self {
+        Self::AssociativeArray(value)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum TemplatePart {
+    Literal(String),
+    Expression {
+        operator: Operator,
+        variables: Vec<VariableSpec>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct VariableSpec {
+    name: String,
+    modifier: Modifier,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Modifier {
+    None,
+    Prefix(usize),
+    Explode,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Operator {
+    Simple,
+    Reserved,
+    Fragment,
+    Label,
+    PathSegment,
+    PathParameter,
+    Query,
+    QueryContinuation,
+}
+
+impl Operator {
+    fn first(self) -> &'static str {
+        match self {
+            Self::Simple | Self::Reserved => "",
+            Self::Fragment => "#",
+            Self::Label => ".",
+            Self::PathSegment => "/",
+            Self::PathParameter => ";",
+            Self::Query => "?",
+            Self::QueryContinuation => "&",
+        }
+    }
+
+    fn separator(self) -> &'static str {
+        match self {
+            Self::Simple | Self::Reserved | Self::Fragment => ",",
+            Self::Label => ".",
+            Self::PathSegment => "/",
+            Self::PathParameter => ";",
+            Self::Query | Self::QueryContinuation => "&",
+        }
+    }
+
+    fn is_named(self) -> bool {
+        matches!(
+            self,
+            Self::PathParameter | Self::Query | Self::QueryContinuation
+        )
+    }
+
+    fn if_empty(self) -> &'static str {
+        match self {
+            Self::Query | Self::QueryContinuation => "=",
+            _ => "",
+        }
+    }
+
+    fn allows_reserved(self) -> bool {
+        matches!(self, Self::Reserved | Self::Fragment)
+    }
+}
+
+fn validate_literal_char(template: &str, position: usize, c: char) -> Result<(), UriTemplateError> {
+    let is_valid = match c {
+        '%' => {
+            let mut escape = template[position + 1..].chars();
+            escape.next().map_or(false, |c| c.is_ascii_hexdigit())
+                && escape.next().map_or(false, |c| c.is_ascii_hexdigit())
+        }
+        ' ' | '"' | '\'' | '<' | '>' | '\\' | '^' | '`' | '{' | '|' | '}' => false,
+        _ => !c.is_ascii_control(),
+    };
+    if is_valid {
+        Ok(())
+    } else {
+        Err(UriTemplateError {
+            kind: UriTemplateErrorKind::InvalidLiteralCharacter { position, c },
+        })
+    }
+}
+
+fn parse_expression(expression: &str, start: usize) -> Result<TemplatePart, UriTemplateError> {
+    let (operator, variable_list) = match expression.chars().next() {
+        Some('+') => (Operator::Reserved, &expression[1..]),
+        Some('#') => (Operator::Fragment, &expression[1..]),
+        Some('.') => (Operator::Label, &expression[1..]),
+        Some('/') => (Operator::PathSegment, &expression[1..]),
+        Some(';') => (Operator::PathParameter, &expression[1..]),
+        Some('?') => (Operator::Query, &expression[1..]),
+        Some('&') => (Operator::QueryContinuation, &expression[1..]),
+        Some(c @ ('=' | ',' | '!' | '@' | '|')) => {
+            return Err(UriTemplateError {
+                kind: UriTemplateErrorKind::ReservedOperator { position: start, c },
+            })
+        }
+        _ => (Operator::Simple, expression),
+    };
+    let mut position = start + expression.len() - variable_list.len();
+    let mut variables = Vec::new();
+    for spec in variable_list.split(',') {
+        variables.push(parse_variable_spec(spec, position)?);
+        position += spec.len() + 1;
+    }
+    Ok(TemplatePart::Expression {
+        operator,
+        variables,
+    })
+}
+
+fn parse_variable_spec(spec: &str, start: usize) -> Result<VariableSpec, UriTemplateError> {
+    let (name, modifier) = if let Some(name) = spec.strip_suffix('*') {
+        (name, Modifier::Explode)
+    } else if let Some((name, length)) = spec.split_once(':') {
+        let is_valid_length = (1..=4).contains(&length.len())
+            && !length.starts_with('0')
+            && length.bytes().all(|b| b.is_ascii_digit());
+        if !is_valid_length {
+            return Err(UriTemplateError {
+                kind: UriTemplateErrorKind::InvalidPrefixLength {
+                    position: start + name.len() + 1,
+                },
+            });
+        }
+        (name, Modifier::Prefix(length.parse().unwrap()))
+    } else {
+        (spec, Modifier::None)
+    };
+    if !is_valid_variable_name(name) {
+        return Err(UriTemplateError {
+            kind: UriTemplateErrorKind::InvalidVariableName { position: start },
+        });
+    }
+    Ok(VariableSpec {
+        name: name.into(),
+        modifier,
+    })
+}
+
+fn is_valid_variable_name(name: &str) -> bool {
+    let bytes = name.as_bytes();
+    let mut i = 0;
+    let mut previous_is_dot = true; // A name is not allowed to start with a dot
+    while i < bytes.len() {
+        match bytes[i] {
+            b'.' if !previous_is_dot => previous_is_dot = true,
+            b'%' if bytes.get(i + 1).map_or(false, u8::is_ascii_hexdigit)
+                && bytes.get(i + 2).map_or(false, u8::is_ascii_hexdigit) =>
+            {
+                previous_is_dot = false;
+                i += 2;
+            }
+            b if b.is_ascii_alphanumeric() || b == b'_' => previous_is_dot = false,
+            _ => return false,
+        }
+        i += 1;
+    }
+    !previous_is_dot
+}
+
+fn expand_expression(
+    operator: Operator,
+    specs: &[VariableSpec],
+    variables: &HashMap<&str, Value>,
+    output: &mut String,
+) -> Result<(), UriTemplateError> {
+    let mut is_first = true;
+    for spec in specs {
+        let value = match variables.get(spec.name.as_str()) {
+            Some(value) if value.is_defined() => value,
+            _ => continue,
+        };
+        output.push_str(if is_first {
+            operator.first()
+        } else {
+            operator.separator()
+        });
+        is_first = false;
+        match value {
+            Value::String(value) => {
+                if operator.is_named() {
+                    output.push_str(&spec.name);
+                    if value.is_empty() {
+                        output.push_str(operator.if_empty());
+                        continue;
+                    }
+                    output.push('=');
+                }
+                let value = if let Modifier::Prefix(length) = spec.modifier {
+                    value
+                        .char_indices()
+                        .nth(length)
+                        .map_or(value.as_str(), |(end, _)| &value[..end])
+                } else {
+                    value
+                };
+                encode(value, operator, output);
+            }
+            Value::List(_) | Value::AssociativeArray(_)
+                if matches!(spec.modifier, Modifier::Prefix(_)) =>
+            {
+                return Err(UriTemplateError {
+                    kind: UriTemplateErrorKind::PrefixOnCompositeValue {
+                        variable: spec.name.clone(),
+                    },
+                });
+            }
+            Value::List(items) => {
+                if spec.modifier == Modifier::Explode {
+                    for (i, item) in items.iter().enumerate() {
+                        if i > 0 {
+                            output.push_str(operator.separator());
+                        }
+                        if operator.is_named() {
+                            output.push_str(&spec.name);
+                            if item.is_empty() {
+                                output.push_str(operator.if_empty());
+                                continue;
+                            }
+                            output.push('=');
+                        }
+                        encode(item, operator, output);
+                    }
+                } else {
+                    let mut joined = String::new();
+                    for (i, item) in items.iter().enumerate() {
+                        if i > 0 {
+                            joined.push(',');
+                        }
+                        encode(item, operator, &mut joined);
+                    }
+                    push_named_composite(operator, spec, &joined, output);
+                }
+            }
+            Value::AssociativeArray(pairs) => {
+                if spec.modifier == Modifier::Explode {
+                    for (i, (key, value)) in pairs.iter().enumerate() {
+                        if i > 0 {
+                            output.push_str(operator.separator());
+                        }
+                        encode(key, operator, output);
+                        if operator.is_named() && value.is_empty() {
+                            output.push_str(operator.if_empty());
+                        } else {
+                            output.push('=');
+                            encode(value, operator, output);
+                        }
+                    }
+                } else {
+                    let mut joined = String::new();
+                    for (i, (key, value)) in pairs.iter().enumerate() {
+                        if i > 0 {
+                            joined.push(',');
+                        }
+                        encode(key, operator, &mut joined);
+                        joined.push(',');
+                        encode(value, operator, &mut joined);
+                    }
+                    push_named_composite(operator, spec, &joined, output);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn push_named_composite(
+    operator: Operator,
+    spec: &VariableSpec,
+    joined: &str,
+    output: &mut String,
+) {
+    if operator.is_named() {
+        output.push_str(&spec.name);
+        output.push_str(if joined.is_empty() {
+            operator.if_empty()
+        } else {
+            "="
+        });
+    }
+    output.push_str(joined);
+}
+
+/// Percent-encodes `value` keeping the unreserved characters and, if the operator allows it, the reserved characters and the existing percent-encoded triplets
+fn encode(value: &str, operator: Operator, output: &mut String) {
+    if !operator.allows_reserved() {
+        percent_encode_into(value, is_unreserved, output).unwrap(); // Writing into a String never fails
+        return;
+    }
+    let mut start = 0;
+    for (i, c) in value.char_indices() {
+        if i < start {
+            continue; // Already written with the percent-encoded triplet
+        }
+        let is_triplet = c == '%'
+            && value
+                .as_bytes()
+                .get(i + 1..i + 3)
+                .map_or(false, |digits| digits.iter().all(u8::is_ascii_hexdigit));
+        if is_triplet {
+            output.push_str(&value[i..i + 3]);
+            start = i + 3;
+        } else {
+            percent_encode_into(
+                &value[i..i + c.len_utf8()],
+                is_unreserved_or_reserved,
+                output,
+            )
+            .unwrap(); // Writing into a String never fails
+        }
+    }
+}
+
+fn is_unreserved(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '_' | '~')
+}
+
+fn is_unreserved_or_reserved(c: char) -> bool {
+    is_unreserved(c)
+        || matches!(
+            c,
+            ':' | '/'
+                | '?'
+                | '#'
+                | '['
+                | ']'
+                | '@'
+                | '!'
+                | '$'
+                | '&'
+                | '\''
+                | '('
+                | ')'
+                | '*'
+                | '+'
+                | ','
+                | ';'
+                | '='
+        )
+}
+
+/// An error raised during [`UriTemplate`] parsing or expansion.
+#[derive(Debug)]
+pub struct UriTemplateError {
+    kind: UriTemplateErrorKind,
+}
+
+impl fmt::Display for UriTemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            UriTemplateErrorKind::InvalidLiteralCharacter { position, c } => {
+                write!(f, "Invalid character '{c}' in template literal at byte {position}")
+            }
+            UriTemplateErrorKind::UnclosedExpression { position } => {
+                write!(f, "The expression starting at byte {position} is not closed")
+            }
+            UriTemplateErrorKind::ReservedOperator { position, c } => {
+                write!(f, "The operator '{c}' at byte {position} is reserved")
+            }
+            UriTemplateErrorKind::InvalidVariableName { position } => {
+                write!(f, "Invalid variable name at byte {position}")
+            }
+            UriTemplateErrorKind::InvalidPrefixLength { position } => write!(
+                f,
+                "Invalid prefix length at byte {position}: expected an integer between 1 and 9999"
+            ),
+            UriTemplateErrorKind::PrefixOnCompositeValue { variable } => write!(
+                f,
+                "The prefix modifier is not applicable to the composite value of variable '{variable}'"
+            ),
+            UriTemplateErrorKind::InvalidIri(e) => {
+                write!(f, "The template expansion is not a valid IRI: {e}")
+            }
+        }
+    }
+}
+
+impl Error for UriTemplateError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        if let UriTemplateErrorKind::InvalidIri(e) = &self.kind {
+            Some(e)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug)]
+enum UriTemplateErrorKind {
+    InvalidLiteralCharacter { position: usize, c: char },
+    UnclosedExpression { position: usize },
+    ReservedOperator { position: usize, c: char },
+    InvalidVariableName { position: usize },
+    InvalidPrefixLength { position: usize },
+    PrefixOnCompositeValue { variable: String },
+    InvalidIri(IriParseError),
+}